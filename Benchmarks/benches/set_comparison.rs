@@ -0,0 +1,174 @@
+use std::cmp::Ordering;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use slot_array::array_tree::ArrayTree;
+use slot_array::sorted_array::SortedArray;
+use slot_array::splay::SplaySet;
+use slot_array::vec_set::VecSet;
+
+mod utils;
+use utils::iter_noop_batched;
+
+const SIZES: [usize; 4] = [10, 100, 1_000, 10_000];
+const SEED: u64 = 42;
+
+fn cmp_u64(a: &u64, b: &u64) -> Ordering {
+    a.cmp(b)
+}
+
+fn random_keys(rng: &mut StdRng, n: usize) -> Vec<u64> {
+    (0 .. n).map(|_| rng.gen()).collect()
+}
+
+fn sequential_keys(n: usize) -> Vec<u64> {
+    (0 .. n as u64).collect()
+}
+
+// All four set types share the same `insert` method but have
+// their own constructor signature, so each workload is spelled out once per
+// structure through this macro rather than through a shared trait.
+macro_rules! bench_build_from_keys {
+    ($group:expr, $n:expr, $keys:expr, [ $( ($label:expr, $new:expr) ),+ $(,)? ]) => {
+        $(
+            $group.bench_with_input(BenchmarkId::new($label, $n), &$n, |b, _| {
+                iter_noop_batched(
+                    b,
+                    |_| $keys.clone(),
+                    |keys: Vec<u64>| keys.len(),
+                    |keys: Vec<u64>| {
+                        let mut set = $new;
+                        let n = keys.len();
+                        for k in keys {
+                            set.insert(k);
+                        }
+                        n
+                    },
+                );
+            });
+        )+
+    };
+}
+
+fn bench_insert_random(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let mut group = c.benchmark_group("insert_random");
+    for &n in SIZES.iter() {
+        let keys = random_keys(&mut rng, n);
+        bench_build_from_keys!(group, n, keys, [
+            ("SplaySet", SplaySet::new(cmp_u64)),
+            ("SortedArray", SortedArray::new(cmp_u64, 20, 4)),
+            ("VecSet", VecSet::new(cmp_u64, 20)),
+            ("ArrayTree", ArrayTree::new(cmp_u64, 16)),
+        ]);
+    }
+    group.finish();
+}
+
+fn bench_insert_sequential(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert_sequential");
+    for &n in SIZES.iter() {
+        let keys = sequential_keys(n);
+        bench_build_from_keys!(group, n, keys, [
+            ("SplaySet", SplaySet::new(cmp_u64)),
+            ("SortedArray", SortedArray::new(cmp_u64, 20, 4)),
+            ("VecSet", VecSet::new(cmp_u64, 20)),
+            ("ArrayTree", ArrayTree::new(cmp_u64, 16)),
+        ]);
+    }
+    group.finish();
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let mut group = c.benchmark_group("lookup");
+
+    for &n in SIZES.iter() {
+        let keys = random_keys(&mut rng, n);
+
+        // Present lookups: `insert` on an already-present key searches for
+        // it and returns false without adding an element, so it exercises
+        // the comparator-driven lookup path. `ArrayTree::insert` still splits
+        // a full target block before it finds the duplicate. All four
+        // structures support `insert`, so this half covers all of them.
+        macro_rules! bench_lookup_present {
+            ($label:expr, $new:expr) => {{
+                let mut set = $new;
+                for k in &keys {
+                    set.insert(*k);
+                }
+
+                group.bench_with_input(BenchmarkId::new(concat!($label, "/present"), n), &n, |b, _| {
+                    iter_noop_batched(
+                        b,
+                        |i| keys[i as usize % keys.len()],
+                        |k: u64| k,
+                        |k: u64| { set.insert(k); k },
+                    );
+                });
+            }};
+        }
+
+        bench_lookup_present!("SplaySet", SplaySet::new(cmp_u64));
+        bench_lookup_present!("SortedArray", SortedArray::new(cmp_u64, 20, 4));
+        bench_lookup_present!("VecSet", VecSet::new(cmp_u64, 20));
+        bench_lookup_present!("ArrayTree", ArrayTree::new(cmp_u64, 16));
+
+        // Absent lookups: `remove` of a key that was never inserted is
+        // likewise a non-mutating search, but `remove` only exists on
+        // `ArrayTree` so far - restrict this half to it until the other
+        // three structures expose a matching `remove`.
+        let mut set = ArrayTree::new(cmp_u64, 16);
+        for k in &keys {
+            set.insert(*k);
+        }
+        group.bench_with_input(BenchmarkId::new("ArrayTree/absent", n), &n, |b, _| {
+            iter_noop_batched(
+                b,
+                |i| u64::MAX - i,
+                |k: u64| k,
+                |k: u64| { set.remove(&k); k },
+            );
+        });
+    }
+
+    group.finish();
+}
+
+// `remove` only exists on `ArrayTree` so far, so this churn workload is
+// restricted to it until the other three structures expose a matching
+// `remove`.
+fn bench_churn(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let mut group = c.benchmark_group("churn");
+
+    for &n in SIZES.iter() {
+        let initial = random_keys(&mut rng, n);
+
+        let mut set = ArrayTree::new(cmp_u64, 16);
+        for k in &initial {
+            set.insert(*k);
+        }
+        let mut churn_rng = StdRng::seed_from_u64(SEED.wrapping_add(1));
+
+        group.bench_with_input(BenchmarkId::new("ArrayTree", n), &n, |b, _| {
+            iter_noop_batched(
+                b,
+                |_| churn_rng.gen::<u64>(),
+                |k: u64| k,
+                |k: u64| {
+                    set.insert(k);
+                    set.remove(&k);
+                    k
+                },
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert_random, bench_insert_sequential, bench_lookup, bench_churn);
+criterion_main!(benches);
@@ -6,35 +6,14 @@ use slot_array::sorted_array::SortedArray;
 use slot_array::array_tree::ArrayTree;
 use slot_array::splay::SplaySet;
 use slot_array::vec_set::VecSet;
+use slot_array::counting_comparator::CountingComparator;
 
 use pretty_assertions::assert_eq;
 
-macro_rules! create_cmp {
-    ($func:ident, $get:ident, $count:ident) => {
-        static mut $count: u64 = 0;
-
-        fn $func(a: &f64, b: &f64) -> std::cmp::Ordering {
-            unsafe {
-                $count += 1;
-            }
-            a.partial_cmp(b).unwrap()
-        }
-
-        fn $get() -> u64 {
-            unsafe {
-                $count
-            }
-        }
-    };
+fn cmp(a: &f64, b: &f64) -> std::cmp::Ordering {
+    a.partial_cmp(b).unwrap()
 }
 
-
-create_cmp!(cmp_a, get_num_calls_a, NUM_CALLS_A);
-create_cmp!(cmp_b, get_num_calls_b, NUM_CALLS_B);
-create_cmp!(cmp_c, get_num_calls_c, NUM_CALLS_C);
-create_cmp!(cmp_d, get_num_calls_d, NUM_CALLS_D);
-
-
 fn main() {
 
     let mut rng = rand::thread_rng();
@@ -42,10 +21,17 @@ fn main() {
     let n = 100;
     let vals: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
 
-    let mut set_a = SplaySet::new(cmp_a);
-    let mut set_b = SortedArray::new(cmp_b, 20, 4);
-    let mut set_c = VecSet::new(cmp_c, 20);
-    let mut set_d = ArrayTree::new(cmp_d, 16);
+    let counted_a = CountingComparator::new(cmp);
+    let counted_b = CountingComparator::new(cmp);
+    let counted_c = CountingComparator::new(cmp);
+    let counted_d = CountingComparator::new(cmp);
+    let (handle_a, handle_b, handle_c, handle_d) =
+        (counted_a.clone(), counted_b.clone(), counted_c.clone(), counted_d.clone());
+
+    let mut set_a = SplaySet::new(move |a: &f64, b: &f64| counted_a.call(a, b));
+    let mut set_b = SortedArray::new(move |a: &f64, b: &f64| counted_b.call(a, b), 20, 4);
+    let mut set_c = VecSet::new(move |a: &f64, b: &f64| counted_c.call(a, b), 20);
+    let mut set_d = ArrayTree::new(move |a: &f64, b: &f64| counted_d.call(a, b), 16);
 
     for x in &vals {
         set_a.insert(*x);
@@ -70,9 +56,9 @@ fn main() {
     assert_eq!(data_a, data_c);
     assert_eq!(data_a, data_d);
 
-    println!("Num calls A: {}", get_num_calls_a());
-    println!("Num calls B: {}", get_num_calls_b());
-    println!("Num calls C: {}", get_num_calls_c());
-    println!("Num calls D: {}", get_num_calls_d());
+    println!("Num calls A: {}", handle_a.count());
+    println!("Num calls B: {}", handle_b.count());
+    println!("Num calls C: {}", handle_c.count());
+    println!("Num calls D: {}", handle_d.count());
 
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
 
 pub struct ArrayTree<T, C>
 where
@@ -6,6 +7,11 @@ where
 {
     comparator: C,
     data: Vec<Vec<T>>,
+    // Per-block element counts (`block_counts[i] == data[i].len()`), kept in
+    // lockstep with `data` so `select`/`rank` can prefix-sum them to locate
+    // the owning block in O(number of blocks)
+    // instead of walking every element via `traverse`.
+    block_counts: Vec<usize>,
     capacity: u16,
     num_elements: usize,
 }
@@ -20,6 +26,7 @@ where
         ArrayTree {
             comparator,
             data,
+            block_counts: Vec::with_capacity(capacity as usize),
             capacity,
             num_elements: 0,
         }
@@ -33,23 +40,15 @@ where
         // println!("\nInserting: {:?}", t);
         if self.data.len() == 0 {
             self.data.push(self.new_block(t));
+            self.block_counts.push(1);
+            self.num_elements += 1;
             return true;
         }
 
         // Binary search for block index
-        let (idx_block, equals) = binary_search_by(
-            &self.data,
-            |block| (self.comparator)(&block[0], &t),
-        );
-        if equals {
-            return false;
-        }
-
-        // Convert from "first larger" to "last smaller" index semantics
-        let mut idx_block = if idx_block > 0 {
-            idx_block - 1
-        } else {
-            0
+        let mut idx_block = match binary_search_by(&self.data, |block| (self.comparator)(&block[0], &t)) {
+            Ok(_) => return false,
+            Err(idx) => idx.saturating_sub(1),
         };
         // println!("idx_block: {}    block_len: {}", idx_block, self.data[idx_block].len());
 
@@ -61,25 +60,26 @@ where
 
             self.data[idx_block].truncate(tail_from);
             self.data.insert(idx_block + 1, block_tail);
+            self.block_counts[idx_block] = self.data[idx_block].len();
+            self.block_counts.insert(idx_block + 1, self.data[idx_block + 1].len());
 
             // println!("block l: {:?}", self.data[idx_block]);
             // println!("block r: {:?}", self.data[idx_block + 1]);
             // Determine into which of the two split blocks the new value goes.
-            // FIXME: Can we miss an "equals" case here if we go into block than doesn't have the equal element?
-            if (self.comparator)(&t, &self.data[idx_block + 1][0]) == Ordering::Greater {
+            // Use "not Less" (Greater or Equal) so a value equal to the right
+            // block's sentinel is routed there, where the value search below
+            // will correctly detect it as a duplicate.
+            if (self.comparator)(&t, &self.data[idx_block + 1][0]) != Ordering::Less {
                 idx_block += 1;
             }
             // println!("idx_block: {}", idx_block);
         }
 
         // Binary search for value index
-        let (idx_value, equals) = binary_search_by(
-            &self.data[idx_block],
-            |x| (self.comparator)(&x, &t),
-        );
-        if equals {
-            return false;
-        }
+        let idx_value = match binary_search_by(&self.data[idx_block], |x| (self.comparator)(x, &t)) {
+            Ok(_) => return false,
+            Err(idx) => idx,
+        };
         // println!("idx_value: {}", idx_value);
 
         // Value insert
@@ -93,9 +93,96 @@ where
         }
 
         self.num_elements += 1;
+        self.block_counts[idx_block] = self.data[idx_block].len();
         true
     }
 
+    pub fn remove(&mut self, t: &T) -> bool {
+        if self.data.len() == 0 {
+            return false;
+        }
+
+        // Binary search for block index (same semantics as insert)
+        let idx_block = match binary_search_by(&self.data, |block| (self.comparator)(&block[0], t)) {
+            Ok(idx) => idx,
+            Err(0) => return false,
+            Err(idx) => idx - 1,
+        };
+
+        // Binary search for value index within the block
+        let idx_value = match binary_search_by(&self.data[idx_block], |x| (self.comparator)(x, t)) {
+            Ok(idx) => idx,
+            Err(_) => return false,
+        };
+
+        self.data[idx_block].remove(idx_value);
+        self.num_elements -= 1;
+        self.block_counts[idx_block] = self.data[idx_block].len();
+
+        self.rebalance(idx_block);
+
+        true
+    }
+
+    // k-th smallest element (0-indexed), found by prefix-summing block_counts.
+    pub fn select(&self, k: usize) -> Option<&T> {
+        if k >= self.num_elements {
+            return None;
+        }
+
+        let mut remaining = k;
+        for (i, &count) in self.block_counts.iter().enumerate() {
+            if remaining < count {
+                return Some(&self.data[i][remaining]);
+            }
+            remaining -= count;
+        }
+        None
+    }
+
+    // Number of elements strictly less than `t`.
+    pub fn rank(&self, t: &T) -> usize {
+        if self.data.len() == 0 {
+            return 0;
+        }
+
+        let idx_block = match binary_search_by(&self.data, |block| (self.comparator)(&block[0], t)) {
+            Ok(idx) => idx,
+            Err(0) => return 0,
+            Err(idx) => idx - 1,
+        };
+
+        let preceding: usize = self.block_counts[.. idx_block].iter().sum();
+
+        let idx_value = binary_search_by(&self.data[idx_block], |x| (self.comparator)(x, t))
+            .unwrap_or_else(|idx| idx);
+
+        preceding + idx_value
+    }
+
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> impl Iterator<Item = &T> {
+        let (start_block, start_idx) = self.start_pos(range.start_bound());
+        let end_bound: Bound<T> = match range.end_bound() {
+            Bound::Included(t) => Bound::Included(t.clone()),
+            Bound::Excluded(t) => Bound::Excluded(t.clone()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let comparator = &self.comparator;
+
+        self.data[start_block..]
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, block)| {
+                let from = if i == 0 { start_idx } else { 0 };
+                block[from..].iter()
+            })
+            .take_while(move |x| match &end_bound {
+                Bound::Unbounded => true,
+                Bound::Included(t) => comparator(x, t) != Ordering::Greater,
+                Bound::Excluded(t) => comparator(x, t) == Ordering::Less,
+            })
+    }
+
     pub fn traverse<F>(&self, mut f: F)
     where
         F: FnMut(usize, &T),
@@ -119,22 +206,99 @@ where
         println!("{:?}", self.data);
     }
 
+    // Finds the (block, index) of the first element not excluded by `bound`,
+    // or `(data.len(), 0)` if every element is excluded.
+    fn start_pos(&self, bound: Bound<&T>) -> (usize, usize) {
+        match bound {
+            Bound::Unbounded => (0, 0),
+            Bound::Included(t) => self.lower_bound_pos(|x| (self.comparator)(x, t) == Ordering::Less),
+            Bound::Excluded(t) => self.lower_bound_pos(|x| (self.comparator)(x, t) != Ordering::Greater),
+        }
+    }
+
+    // Partition-point search for the first element for which `is_before` is
+    // false, scanning block sentinels first and then the one block that may
+    // straddle the boundary.
+    fn lower_bound_pos<F>(&self, mut is_before: F) -> (usize, usize)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if self.data.len() == 0 {
+            return (0, 0);
+        }
+
+        // Neither search below ever returns `Equal`, so both always land in `Err`.
+        let b = binary_search_by(
+            &self.data,
+            |block| if is_before(&block[0]) { Ordering::Less } else { Ordering::Greater },
+        ).unwrap_err();
+
+        if b > 0 {
+            let idx = binary_search_by(
+                &self.data[b - 1],
+                |x| if is_before(x) { Ordering::Less } else { Ordering::Greater },
+            ).unwrap_err();
+            if idx < self.data[b - 1].len() {
+                return (b - 1, idx);
+            }
+        }
+
+        (b, 0)
+    }
+
     fn new_block(&self, t: T) -> Vec<T> {
         let mut block = Vec::with_capacity(self.capacity as usize);
         block.push(t);
         block
     }
+
+    // Merges `data[idx_block]` into a neighbour whenever it has dropped below
+    // `capacity / 2` elements, re-splitting the merged block if it now exceeds
+    // `capacity`. Blocks that end up empty are dropped entirely so that
+    // `block[0]` sentinels keep pointing at real elements.
+    fn rebalance(&mut self, idx_block: usize) {
+        let min_len = (self.capacity / 2) as usize;
+        if self.data[idx_block].len() >= min_len {
+            return;
+        }
+
+        if self.data.len() <= 1 {
+            if self.data[idx_block].is_empty() {
+                self.data.remove(idx_block);
+                self.block_counts.remove(idx_block);
+            }
+            return;
+        }
+
+        let merge_idx = if idx_block + 1 < self.data.len() {
+            idx_block
+        } else {
+            idx_block - 1
+        };
+
+        let next = self.data.remove(merge_idx + 1);
+        self.block_counts.remove(merge_idx + 1);
+        self.data[merge_idx].extend(next);
+        self.block_counts[merge_idx] = self.data[merge_idx].len();
+
+        if self.data[merge_idx].len() > self.capacity as usize {
+            let split_at = self.data[merge_idx].len() / 2;
+            let tail = self.data[merge_idx].split_off(split_at);
+            self.data.insert(merge_idx + 1, tail);
+            self.block_counts[merge_idx] = self.data[merge_idx].len();
+            self.block_counts.insert(merge_idx + 1, self.data[merge_idx + 1].len());
+        }
+    }
 }
 
 
-pub fn binary_search_by<T, F>(data: &[T], mut f: F) -> (usize, bool)
+// Mirrors `slice::binary_search_by`: `Ok(idx)` on an exact comparator match,
+// `Err(idx)` with the insertion point otherwise.
+pub fn binary_search_by<T, F>(data: &[T], mut f: F) -> Result<usize, usize>
 where
     F: FnMut(&T) -> Ordering,
     T: std::fmt::Debug,
 {
-    if data.len() == 0 {
-        return (data.len(), false);
-    }
     let mut l: usize = 0;
     let mut r: usize = data.len();
 
@@ -150,7 +314,7 @@ where
                 r = mid;
             }
             Ordering::Equal => {
-                return (mid, true)
+                return Ok(mid)
             }
             Ordering::Less => {
                 l = mid + 1;
@@ -158,7 +322,7 @@ where
         }
     }
 
-    (r, false)
+    Err(r)
 }
 
 
@@ -195,11 +359,13 @@ mod test {
     macro_rules! new_array {
         ($capacity:expr, $data:expr) => {{
             let data: Vec<Vec<i32>> = $data;
+            let block_counts = data.iter().map(|block| block.len()).collect();
             let num_elements = data.iter().map(|block| block.len()).sum();
             ArrayTree {
                 comparator: int_comparator,
                 capacity: $capacity,
                 data: $data,
+                block_counts,
                 num_elements,
             }
         }};
@@ -261,6 +427,18 @@ mod test {
         assert_eq!(at.num_elements, 5);
     }
 
+    #[test]
+    fn test_array_tree_split_rejects_duplicate_of_new_sentinel() {
+        // Regression test: inserting a value equal to the new right block's
+        // sentinel (created by splitting the one full block) must be
+        // rejected as a duplicate rather than silently re-inserted into the
+        // left half.
+        let mut at = new_array!(2, vec![vec![2, 3]]);
+        assert_eq!(at.insert(3), false);
+        assert_eq!(at.data, [vec![2], vec![3]]);
+        assert_eq!(at.num_elements, 2);
+    }
+
     #[test]
     fn test_array_tree_collect() {
         for cap in vec![2, 3, 4, 5] {
@@ -274,6 +452,104 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_array_tree_remove_basic() {
+        let mut at = new_array!(2, vec![vec![2, 4], vec![6, 8]]);
+        assert_eq!(at.remove(&4), true);
+        assert_eq!(at.data, [vec![2], vec![6, 8]]);
+        assert_eq!(at.num_elements, 3);
+
+        assert_eq!(at.remove(&5), false);
+        assert_eq!(at.num_elements, 3);
+    }
+
+    #[test]
+    fn test_array_tree_remove_merges_underflowing_block() {
+        let mut at = new_array!(4, vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(at.remove(&1), true);
+        assert_eq!(at.data, [vec![2, 3, 4]]);
+        assert_eq!(at.num_elements, 3);
+    }
+
+    #[test]
+    fn test_array_tree_remove_resplits_oversized_merge() {
+        let mut at = new_array!(4, vec![vec![1], vec![2, 3, 4, 5, 6]]);
+        assert_eq!(at.remove(&7), false);
+        assert_eq!(at.remove(&1), true);
+        assert_eq!(at.data, [vec![2, 3], vec![4, 5, 6]]);
+        assert_eq!(at.num_elements, 5);
+    }
+
+    #[test]
+    fn test_array_tree_remove_roundtrip() {
+        let mut at = ArrayTree::new(int_comparator, 4);
+        insert_many!(at, [1, 2, 3, 4, 5, 6, 7, 8]);
+        for x in [3, 1, 8, 5, 2, 4, 6, 7].iter() {
+            assert_eq!(at.remove(x), true);
+        }
+        assert_eq!(at.collect(), Vec::<i32>::new());
+        assert_eq!(at.num_elements, 0);
+    }
+
+    #[test]
+    fn test_array_tree_range_inclusive_exclusive() {
+        let mut at = ArrayTree::new(int_comparator, 4);
+        insert_many!(at, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(at.range(3..=6).cloned().collect::<Vec<_>>(), [3, 4, 5, 6]);
+        assert_eq!(at.range(3..6).cloned().collect::<Vec<_>>(), [3, 4, 5]);
+        assert_eq!(at.range(..4).cloned().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(at.range(6..).cloned().collect::<Vec<_>>(), [6, 7, 8]);
+        assert_eq!(at.range(..).cloned().collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_array_tree_range_missing_bounds() {
+        let mut at = ArrayTree::new(int_comparator, 4);
+        insert_many!(at, [1, 3, 5, 7, 9]);
+
+        assert_eq!(at.range(4..=6).cloned().collect::<Vec<_>>(), [5]);
+        assert_eq!(at.range(10..).cloned().collect::<Vec<_>>(), Vec::<i32>::new());
+        assert_eq!(at.range(..0).cloned().collect::<Vec<_>>(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_array_tree_select() {
+        let mut at = ArrayTree::new(int_comparator, 4);
+        insert_many!(at, [5, 1, 8, 3, 2, 7, 4, 6]);
+
+        for k in 0 .. 8 {
+            assert_eq!(at.select(k), Some(&((k + 1) as i32)));
+        }
+        assert_eq!(at.select(8), None);
+    }
+
+    #[test]
+    fn test_array_tree_rank() {
+        let mut at = ArrayTree::new(int_comparator, 4);
+        insert_many!(at, [5, 1, 8, 3, 2, 7, 4, 6]);
+
+        assert_eq!(at.rank(&1), 0);
+        assert_eq!(at.rank(&4), 3);
+        assert_eq!(at.rank(&8), 7);
+        assert_eq!(at.rank(&0), 0);
+        assert_eq!(at.rank(&9), 8);
+    }
+
+    #[test]
+    fn test_array_tree_rank_select_after_remove() {
+        let mut at = ArrayTree::new(int_comparator, 4);
+        insert_many!(at, [1, 2, 3, 4, 5, 6, 7, 8]);
+        at.remove(&4);
+        at.remove(&1);
+
+        let expected = [2, 3, 5, 6, 7, 8];
+        for (k, x) in expected.iter().enumerate() {
+            assert_eq!(at.select(k), Some(x));
+        }
+        assert_eq!(at.rank(&6), 3);
+    }
+
     //#[ignore]
     #[test]
     fn test_failing() {
@@ -0,0 +1,88 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+// Wraps a comparator and counts how many times it is invoked, without
+// resorting to the `static mut` counters the examples used to reach for.
+// The count lives behind an `Rc<Cell<u64>>` so a cheap `.clone()` can be
+// handed to the closure that's actually passed as the comparator, while the
+// original handle keeps read access to the running total.
+#[derive(Clone)]
+pub struct CountingComparator<C> {
+    inner: C,
+    count: Rc<Cell<u64>>,
+}
+
+impl<C> CountingComparator<C> {
+    pub fn new(inner: C) -> CountingComparator<C> {
+        CountingComparator {
+            inner,
+            count: Rc::new(Cell::new(0)),
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.get()
+    }
+
+    pub fn reset(&self) {
+        self.count.set(0);
+    }
+
+    // Stable Rust can't implement the `Fn` traits for a custom type, so
+    // callers wrap this in a closure, e.g. `|a, b| counted.call(a, b)`.
+    pub fn call<T>(&self, a: &T, b: &T) -> Ordering
+    where
+        C: Fn(&T, &T) -> Ordering,
+    {
+        self.count.set(self.count.get() + 1);
+        (self.inner)(a, b)
+    }
+}
+
+// Runs `workload` against a `CountingComparator` wrapping `comparator` and
+// returns how many comparisons it performed. `workload` is handed a clone of
+// the counter so it can move it into whatever closure it passes on as the
+// actual `Fn(&T, &T) -> Ordering` comparator.
+pub fn comparison_count<T, C, F>(comparator: C, workload: F) -> u64
+where
+    C: Fn(&T, &T) -> Ordering + Clone,
+    F: FnOnce(CountingComparator<C>),
+{
+    let counted = CountingComparator::new(comparator);
+    let handle = counted.clone();
+    workload(counted);
+    handle.count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn int_comparator(a: &i32, b: &i32) -> Ordering {
+        a.cmp(b)
+    }
+
+    #[test]
+    fn test_counting_comparator_counts_calls() {
+        let counted = CountingComparator::new(int_comparator);
+        assert_eq!(counted.count(), 0);
+
+        assert_eq!(counted.call(&1, &2), Ordering::Less);
+        assert_eq!(counted.call(&2, &1), Ordering::Greater);
+        assert_eq!(counted.count(), 2);
+
+        counted.reset();
+        assert_eq!(counted.count(), 0);
+    }
+
+    #[test]
+    fn test_comparison_count_helper() {
+        let n_calls = comparison_count(int_comparator, |counted| {
+            for x in [3, 1, 4, 1, 5].iter() {
+                counted.call(x, &1);
+            }
+        });
+        assert_eq!(n_calls, 5);
+    }
+}
@@ -4,24 +4,11 @@ use rand::Rng;
 
 use sorted_slot_array::sorted_array::SortedArray;
 use sorted_slot_array::splay::SplaySet;
+use sorted_slot_array::counting_comparator::CountingComparator;
 
 use pretty_assertions::assert_eq;
 
-static mut NUM_CALLS_A: u64 = 0;
-static mut NUM_CALLS_B: u64 = 0;
-
-
-fn cmp_a(a: &f64, b: &f64) -> std::cmp::Ordering {
-    unsafe {
-        NUM_CALLS_A += 1;
-    }
-    a.partial_cmp(b).unwrap()
-}
-
-fn cmp_b(a: &f64, b: &f64) -> std::cmp::Ordering {
-    unsafe {
-        NUM_CALLS_B += 1;
-    }
+fn cmp(a: &f64, b: &f64) -> std::cmp::Ordering {
     a.partial_cmp(b).unwrap()
 }
 
@@ -32,8 +19,12 @@ fn main() {
     let n = 100;
     let vals: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
 
-    let mut set_a = SplaySet::new(cmp_a);
-    let mut set_b = SortedArray::new(cmp_b, 20, 4);
+    let counted_a = CountingComparator::new(cmp);
+    let counted_b = CountingComparator::new(cmp);
+    let (handle_a, handle_b) = (counted_a.clone(), counted_b.clone());
+
+    let mut set_a = SplaySet::new(move |a: &f64, b: &f64| counted_a.call(a, b));
+    let mut set_b = SortedArray::new(move |a: &f64, b: &f64| counted_b.call(a, b), 20, 4);
 
     for x in &vals {
         set_a.insert(*x);
@@ -49,9 +40,7 @@ fn main() {
     assert_eq!(data_b.len(), n);
     assert_eq!(data_a, data_b);
 
-    unsafe {
-        println!("Num calls A: {}", NUM_CALLS_A);
-        println!("Num calls B: {}", NUM_CALLS_B);
-    }
+    println!("Num calls A: {}", handle_a.count());
+    println!("Num calls B: {}", handle_b.count());
 
 }
\ No newline at end of file